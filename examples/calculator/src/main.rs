@@ -2,9 +2,10 @@ use lazy_static::lazy_static;
 use pest_derive::Parser;
 use pest::Parser;
 
-
+use pest::error::{Error as PestError, ErrorVariant};
 use pest::iterators::Pairs;
 use pest::pratt_parser::{Assoc, Op, PrattParser};
+use pest::Span;
 use std::io::BufRead;
 
 #[derive(Parser)]
@@ -23,20 +24,79 @@ lazy_static! {
     };
 }
 
-fn eval(expression: Pairs<Rule>) -> f64 {
+/// An evaluation-time failure, carrying the source `Span` that caused it so
+/// the REPL can underline the offending text instead of panicking.
+#[derive(Debug)]
+enum EvalError<'i> {
+    DivisionByZero(Span<'i>),
+    Overflow(Span<'i>),
+    NumberParse(Span<'i>),
+}
+
+impl<'i> EvalError<'i> {
+    fn span(&self) -> Span<'i> {
+        match self {
+            EvalError::DivisionByZero(span)
+            | EvalError::Overflow(span)
+            | EvalError::NumberParse(span) => *span,
+        }
+    }
+
+    fn message(&self) -> String {
+        match self {
+            EvalError::DivisionByZero(_) => "division by zero".to_string(),
+            EvalError::Overflow(_) => "result is too large to represent".to_string(),
+            EvalError::NumberParse(_) => "not a valid number".to_string(),
+        }
+    }
+}
+
+impl<'i> std::fmt::Display for EvalError<'i> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let error: PestError<Rule> = PestError::new_from_span(
+            ErrorVariant::CustomError {
+                message: self.message(),
+            },
+            self.span(),
+        );
+        write!(f, "{}", error)
+    }
+}
+
+fn eval(expression: Pairs<Rule>) -> Result<f64, EvalError> {
     PRATT_PARSER
         .map_primary(|primary| match primary.as_rule() {
-            Rule::num => primary.as_str().parse::<f64>().unwrap(),
+            Rule::num => primary
+                .as_str()
+                .parse::<f64>()
+                .map_err(|_| EvalError::NumberParse(primary.as_span())),
             Rule::expr => eval(primary.into_inner()),
             _ => unreachable!(),
         })
-        .map_infix(|lhs, op, rhs| match op.as_rule() {
-            Rule::add => lhs + rhs,
-            Rule::subtract => lhs - rhs,
-            Rule::multiply => lhs * rhs,
-            Rule::divide => lhs / rhs,
-            Rule::power => lhs.powf(rhs),
-            _ => unreachable!(),
+        .map_infix(|lhs, op, rhs| {
+            let lhs = lhs?;
+            let rhs = rhs?;
+            match op.as_rule() {
+                Rule::add => Ok(lhs + rhs),
+                Rule::subtract => Ok(lhs - rhs),
+                Rule::multiply => Ok(lhs * rhs),
+                Rule::divide => {
+                    if rhs == 0.0 {
+                        Err(EvalError::DivisionByZero(op.as_span()))
+                    } else {
+                        Ok(lhs / rhs)
+                    }
+                }
+                Rule::power => {
+                    let result = lhs.powf(rhs);
+                    if result.is_infinite() {
+                        Err(EvalError::Overflow(op.as_span()))
+                    } else {
+                        Ok(result)
+                    }
+                }
+                _ => unreachable!(),
+            }
         })
         .parse(expression)
 }
@@ -48,13 +108,13 @@ fn main() {
         let line = line.unwrap().trim().to_string();
         let parse_result = Calculator::parse(Rule::calculation, &line);
         match parse_result {
-            Ok(mut calc) => println!(
-                " = {}",
-                eval(
-                    // inner of expr
-                    calc.next().unwrap().into_inner()
-                )
-            ),
+            Ok(mut calc) => {
+                // inner of expr
+                match eval(calc.next().unwrap().into_inner()) {
+                    Ok(value) => println!(" = {}", value),
+                    Err(e) => println!("{}", e),
+                }
+            }
             Err(_) => println!(" Syntax error"),
         }
     }
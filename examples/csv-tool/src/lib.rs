@@ -0,0 +1,204 @@
+extern crate pest;
+#[macro_use]
+extern crate pest_derive;
+
+use pest::iterators::Pair;
+use pest::Parser;
+use std::fmt;
+use std::io::{self, BufRead};
+
+const _GRAMMAR: &str = include_str!("csv.pest");
+
+#[derive(Parser)]
+#[grammar = "csv.pest"]
+struct CsvParser;
+
+/// Controls how [`CsvReader`] splits and reads records.
+///
+/// `delimiter` only supports `,` for now; the grammar the reader parses
+/// records with is fixed at compile time, so anything else is rejected by
+/// [`CsvReader::new`] rather than silently mis-parsed.
+#[derive(Debug, Clone)]
+pub struct CsvOptions {
+    pub delimiter: char,
+    pub has_header: bool,
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        CsvOptions {
+            delimiter: ',',
+            has_header: false,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum CsvError {
+    Io(io::Error),
+    UnsupportedDelimiter(char),
+    Parse(String),
+}
+
+impl fmt::Display for CsvError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CsvError::Io(e) => write!(f, "I/O error: {}", e),
+            CsvError::UnsupportedDelimiter(c) => {
+                write!(f, "unsupported delimiter '{}': only ',' is implemented", c)
+            }
+            CsvError::Parse(line) => write!(f, "invalid CSV record: {:?}", line),
+        }
+    }
+}
+
+impl std::error::Error for CsvError {}
+
+impl From<io::Error> for CsvError {
+    fn from(e: io::Error) -> Self {
+        CsvError::Io(e)
+    }
+}
+
+/// Reads CSV records one row at a time from any [`BufRead`], rather than
+/// buffering the whole file into a `String` up front.
+///
+/// A record is read with [`BufRead::read_line`] rather than
+/// [`BufRead::lines`]: `lines` splits strictly on `\n` before any CSV
+/// parsing happens, which would cut a quoted field containing a literal
+/// newline (see `csv.pest`) into two bogus rows. Instead, [`read_record`]
+/// keeps reading physical lines and rejoining them with `\n` as long as the
+/// record so far has an unbalanced (odd) number of `"`, i.e. we're still
+/// inside an open quoted field.
+pub struct CsvReader<R> {
+    reader: R,
+    header: Option<Vec<String>>,
+}
+
+impl<R: BufRead> CsvReader<R> {
+    pub fn new(mut reader: R, options: CsvOptions) -> Result<Self, CsvError> {
+        if options.delimiter != ',' {
+            return Err(CsvError::UnsupportedDelimiter(options.delimiter));
+        }
+
+        let header = if options.has_header {
+            match read_record(&mut reader)? {
+                Some(line) => Some(parse_record(&line)?),
+                None => None,
+            }
+        } else {
+            None
+        };
+
+        Ok(CsvReader { reader, header })
+    }
+
+    /// The header row, if `CsvOptions::has_header` was set and the input
+    /// wasn't empty.
+    pub fn header(&self) -> Option<&[String]> {
+        self.header.as_deref()
+    }
+}
+
+impl<R: BufRead> Iterator for CsvReader<R> {
+    type Item = Result<Vec<String>, CsvError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let line = match read_record(&mut self.reader) {
+                Ok(Some(line)) => line,
+                Ok(None) => return None,
+                Err(e) => return Some(Err(e)),
+            };
+            if line.is_empty() {
+                continue;
+            }
+            return Some(parse_record(&line));
+        }
+    }
+}
+
+/// Reads one logical record's worth of text, accumulating further physical
+/// lines while the text read so far has an open (unbalanced) quote, so a
+/// quoted field spanning multiple physical lines is handed to the parser as
+/// a single record. Returns `Ok(None)` at EOF with nothing left to read.
+fn read_record<R: BufRead>(reader: &mut R) -> Result<Option<String>, CsvError> {
+    let mut record = String::new();
+    let mut saw_any = false;
+
+    loop {
+        let mut line = String::new();
+        let bytes_read = reader.read_line(&mut line)?;
+        if bytes_read == 0 {
+            break;
+        }
+        saw_any = true;
+
+        let had_newline = line.ends_with('\n');
+        let line = line.trim_end_matches('\n').trim_end_matches('\r');
+        if !record.is_empty() {
+            // The line break was trimmed above, but a newline embedded in an
+            // open quoted field is part of the record's actual content.
+            record.push('\n');
+        }
+        record.push_str(line);
+
+        if !had_newline || !has_unbalanced_quote(&record) {
+            break;
+        }
+    }
+
+    Ok(if saw_any { Some(record) } else { None })
+}
+
+fn has_unbalanced_quote(text: &str) -> bool {
+    text.chars().filter(|&c| c == '"').count() % 2 == 1
+}
+
+fn parse_record(line: &str) -> Result<Vec<String>, CsvError> {
+    let record = CsvParser::parse(Rule::record, line)
+        .map_err(|_| CsvError::Parse(line.to_string()))?
+        .next()
+        .expect("Rule::record always produces one pair");
+
+    Ok(record
+        .into_inner()
+        .filter(|pair| pair.as_rule() == Rule::field)
+        .map(field_value)
+        .collect())
+}
+
+fn field_value(field: Pair<Rule>) -> String {
+    let inner = field
+        .into_inner()
+        .next()
+        .expect("field always contains a quoted_field or unquoted_field");
+    match inner.as_rule() {
+        Rule::quoted_field => {
+            let quoted_inner = inner.into_inner().next().unwrap();
+            quoted_inner.as_str().replace("\"\"", "\"")
+        }
+        Rule::unquoted_field => inner.as_str().to_string(),
+        rule => unreachable!("field expected quoted_field or unquoted_field, found {:?}", rule),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn a_quoted_field_with_an_embedded_newline_is_read_as_one_record() {
+        let input = "a,\"b\nc\",d\ne,f,g\n";
+        let mut reader = CsvReader::new(Cursor::new(input), CsvOptions::default()).unwrap();
+
+        let first = reader.next().unwrap().unwrap();
+        assert_eq!(first, vec!["a", "b\nc", "d"]);
+
+        let second = reader.next().unwrap().unwrap();
+        assert_eq!(second, vec!["e", "f", "g"]);
+
+        assert!(reader.next().is_none());
+    }
+}
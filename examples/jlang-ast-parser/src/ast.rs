@@ -0,0 +1,156 @@
+use std::ffi::CString;
+
+use from_pest::ConversionError;
+use from_pest::FromPest;
+use pest::iterators::{Pair, Pairs};
+use pest::Span;
+use pest_ast::FromPest;
+use void::Void;
+
+use crate::Rule;
+
+fn span_into_string(span: Span) -> String {
+    span.as_str().to_string()
+}
+
+fn span_into_string_literal(span: Span) -> CString {
+    let raw = span.as_str();
+    // Strip the leading and trailing quote, then unescape doubled quotes.
+    let inner = &raw[1..raw.len() - 1];
+    CString::new(inner.replace("''", "'")).expect("j strings may not contain NUL bytes")
+}
+
+#[derive(Debug, FromPest)]
+#[pest_ast(rule(Rule::program))]
+pub struct Program {
+    pub exprs: Vec<Expr>,
+    eoi: Eoi,
+}
+
+#[derive(Debug, FromPest)]
+#[pest_ast(rule(Rule::EOI))]
+struct Eoi;
+
+#[derive(Debug, FromPest)]
+#[pest_ast(rule(Rule::ident))]
+pub struct Ident {
+    #[pest_ast(outer(with(span_into_string)))]
+    pub name: String,
+}
+
+#[derive(Debug, FromPest)]
+#[pest_ast(rule(Rule::integer))]
+pub struct Integer {
+    #[pest_ast(outer(with(span_into_string)))]
+    pub digits: String,
+}
+
+#[derive(Debug, FromPest)]
+#[pest_ast(rule(Rule::decimal))]
+pub struct Decimal {
+    #[pest_ast(outer(with(span_into_string)))]
+    pub digits: String,
+}
+
+#[derive(Debug, FromPest)]
+#[pest_ast(rule(Rule::string))]
+pub struct Str {
+    #[pest_ast(outer(with(span_into_string_literal)))]
+    pub value: CString,
+}
+
+#[derive(Debug, FromPest)]
+#[pest_ast(rule(Rule::verb))]
+pub struct Verb {
+    #[pest_ast(outer(with(span_into_string)))]
+    pub symbol: String,
+}
+
+#[derive(Debug, FromPest)]
+#[pest_ast(rule(Rule::adverb))]
+pub struct Adverb {
+    #[pest_ast(outer(with(span_into_string)))]
+    pub symbol: String,
+}
+
+#[derive(Debug, FromPest)]
+#[pest_ast(rule(Rule::action))]
+pub struct Action {
+    pub verb: Verb,
+    pub adverbs: Vec<Adverb>,
+}
+
+#[derive(Debug, FromPest)]
+#[pest_ast(rule(Rule::terms))]
+pub struct Terms {
+    pub terms: Vec<Term>,
+}
+
+#[derive(Debug)]
+pub enum Term {
+    Decimal(Decimal),
+    Integer(Integer),
+    Ident(Ident),
+    Parenthesized(Box<Expr>),
+}
+
+#[derive(Debug, FromPest)]
+#[pest_ast(rule(Rule::monadicExpr))]
+pub struct MonadicExpr {
+    pub action: Action,
+    pub expr: Box<Expr>,
+}
+
+#[derive(Debug, FromPest)]
+#[pest_ast(rule(Rule::dyadicExpr))]
+pub struct DyadicExpr {
+    // `dyadicExpr = { (terms | decimal | integer | ident) ~ action ~ expr }`,
+    // but `terms = { term+ }` already matches a lone term, so in PEG ordered
+    // choice the `decimal | integer | ident` alternatives are unreachable:
+    // the lhs pair is always `Rule::terms`.
+    pub lhs: Terms,
+    pub action: Action,
+    pub expr: Box<Expr>,
+}
+
+#[derive(Debug, FromPest)]
+#[pest_ast(rule(Rule::assgmtExpr))]
+pub struct AssgmtExpr {
+    pub ident: Ident,
+    pub expr: Box<Expr>,
+}
+
+#[derive(Debug, FromPest)]
+#[pest_ast(rule(Rule::expr))]
+pub enum Expr {
+    Assignment(AssgmtExpr),
+    Dyadic(DyadicExpr),
+    Monadic(MonadicExpr),
+    Terms(Terms),
+    Str(Str),
+}
+
+impl<'pest> FromPest<'pest> for Term {
+    type Rule = Rule;
+    type FatalError = Void;
+
+    fn from_pest(pairs: &mut Pairs<'pest, Rule>) -> Result<Self, ConversionError<Void>> {
+        let mut clone = pairs.clone();
+        let pair: Pair<'pest, Rule> = clone.next().ok_or(ConversionError::NoMatch)?;
+        let term = match pair.as_rule() {
+            Rule::decimal => Term::Decimal(Decimal::from_pest(pairs)?),
+            Rule::integer => Term::Integer(Integer::from_pest(pairs)?),
+            Rule::ident => Term::Ident(Ident::from_pest(pairs)?),
+            Rule::expr => Term::Parenthesized(Box::new(Expr::from_pest(pairs)?)),
+            _ => return Err(ConversionError::NoMatch),
+        };
+        Ok(term)
+    }
+}
+
+/// Converts the `Pairs` of a `Rule::program` match into a typed AST, checked
+/// at compile time against `Rule`
+/// rather than walked by hand with `build_ast_from_expr`-style dispatch.
+pub fn parse(mut pairs: Pairs<Rule>) -> Result<Program, ConversionError<Void>> {
+    Program::from_pest(&mut pairs)
+}
@@ -0,0 +1,46 @@
+extern crate pest;
+#[macro_use]
+extern crate pest_derive;
+
+mod ast;
+
+use from_pest::FromPest;
+use pest::error::Error;
+use pest::Parser;
+
+#[derive(Parser)]
+#[grammar = "j.pest"]
+pub struct JParser;
+
+pub fn parse(source: &str) -> Result<ast::Program, Error<Rule>> {
+    let mut pairs = JParser::parse(Rule::program, source)?;
+    // `Program::from_pest` walks `pairs` itself; it never fails on a tree
+    // that already matched the grammar, so the conversion error can't
+    // surface here in practice.
+    let program = ast::Program::from_pest(&mut pairs).expect("infallible conversion");
+    Ok(program)
+}
+
+fn main() {
+    let unparsed_file = std::fs::read_to_string("example.ijs").expect("cannot read ijs file");
+    let program = parse(&unparsed_file).expect("unsuccessful parse");
+    println!("{:#?}", program.exprs);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dyadic_expr_parses_without_panicking() {
+        let program = parse("2 + 3").expect("unsuccessful parse");
+        assert_eq!(program.exprs.len(), 1);
+        match &program.exprs[0] {
+            ast::Expr::Dyadic(dyadic) => {
+                assert_eq!(dyadic.lhs.terms.len(), 1);
+                assert_eq!(dyadic.action.verb.symbol, "+");
+            }
+            other => panic!("expected a dyadic expression, got {:?}", other),
+        }
+    }
+}
@@ -2,7 +2,10 @@ extern crate pest;
 #[macro_use]
 extern crate pest_derive;
 
+use lazy_static::lazy_static;
 use pest::error::Error;
+use pest::iterators::Pairs;
+use pest::pratt_parser::{Op, PrattParser};
 use std::ffi::CString;
 use self::AstNode::*;
 use pest::Parser;
@@ -11,6 +14,49 @@ use pest::Parser;
 #[grammar = "j.pest"]
 pub struct JParser;
 
+/// A verb, optionally modified by one adverb: `/` (reduce/insert) or `\`
+/// (scan). Parsed with a `PrattParser` that treats `adverb` as a postfix
+/// operator on `verb`; a second adverb on the same verb panics.
+lazy_static! {
+    static ref VERB_PRATT: PrattParser<Rule> = {
+        use Rule::*;
+
+        PrattParser::new().op(Op::postfix(adverb))
+    };
+}
+
+#[derive(PartialEq, Eq, Debug, Clone)]
+enum VerbPhrase {
+    Plain(String),
+    Reduce(String),
+    Scan(String),
+}
+
+fn parse_verb_phrase(pairs: Pairs<Rule>) -> VerbPhrase {
+    VERB_PRATT
+        .map_primary(|primary| match primary.as_rule() {
+            Rule::verb => VerbPhrase::Plain(primary.as_str().to_string()),
+            rule => unreachable!("VerbPhrase::parse expected verb, found {:?}", rule),
+        })
+        .map_postfix(|verb, op| match op.as_rule() {
+            Rule::adverb => {
+                let v = match verb {
+                    VerbPhrase::Plain(v) => v,
+                    VerbPhrase::Reduce(v) | VerbPhrase::Scan(v) => {
+                        panic!("'{}' already has an adverb applied", v)
+                    }
+                };
+                match op.as_str() {
+                    "/" => VerbPhrase::Reduce(v),
+                    "\\" => VerbPhrase::Scan(v),
+                    adverb => unreachable!("grammar only allows '/' or '\\\\' adverbs, got {:?}", adverb),
+                }
+            }
+            rule => unreachable!("VerbPhrase::parse expected adverb, found {:?}", rule),
+        })
+        .parse(pairs)
+}
+
 #[derive(PartialEq, Eq, Debug, Clone)]
 pub enum MonadicVerb {
     Increment = 1,
@@ -82,19 +128,19 @@ fn build_ast_from_expr(pair: pest::iterators::Pair<Rule>) -> AstNode {
         Rule::expr => build_ast_from_expr(pair.into_inner().next().unwrap()),
         Rule::monadicExpr => {
             let mut pair = pair.into_inner();
-            let action = pair.next().unwrap();
+            let verb_phrase = parse_verb_phrase(pair.next().unwrap().into_inner());
             let expr = pair.next().unwrap();
             let expr = build_ast_from_expr(expr);
-            parse_monadic_action(action, expr)
+            parse_monadic_action(verb_phrase, expr)
         },
         Rule::dyadicExpr => {
             let mut pair = pair.into_inner();
             let lhspair = pair.next().unwrap();
             let lhs = build_ast_from_expr(lhspair);
-            let action = pair.next().unwrap();
+            let verb_phrase = parse_verb_phrase(pair.next().unwrap().into_inner());
             let rhspair = pair.next().unwrap();
             let rhs = build_ast_from_expr(rhspair);
-            parse_dyadic_action(action, lhs, rhs)
+            parse_dyadic_action(verb_phrase, lhs, rhs)
         },
         Rule::terms => {
             let terms : Vec<AstNode>= pair.into_inner()
@@ -127,15 +173,16 @@ fn build_ast_from_expr(pair: pest::iterators::Pair<Rule>) -> AstNode {
     }
 }
 
-fn parse_dyadic_action(pair : pest::iterators::Pair<Rule>,
-                       lhs : AstNode,
-                       rhs : AstNode) -> AstNode {
-    let mut pair = pair.into_inner();
-    let verb = pair.next().unwrap();
-    let adverbs : Vec<pest::iterators::Pair<_>> = pair.collect();
-
-    // Adverbs not currently supported on dyadic verbs.
-    assert_eq!(adverbs.len(), 0);
+fn parse_dyadic_action(verb_phrase: VerbPhrase, lhs: AstNode, rhs: AstNode) -> AstNode {
+    let verb = match verb_phrase {
+        VerbPhrase::Plain(verb) => verb,
+        VerbPhrase::Reduce(verb) => {
+            panic!("adverbs are not supported on dyadic verbs: '{}/'", verb)
+        }
+        VerbPhrase::Scan(verb) => {
+            panic!("adverbs are not supported on dyadic verbs: '{}\\'", verb)
+        }
+    };
 
     let lhs = Box::new(lhs);
     let rhs = Box::new(rhs);
@@ -158,69 +205,33 @@ fn parse_dyadic_action(pair : pest::iterators::Pair<Rule>,
     }
 }
 
-fn parse_monadic_action(pair : pest::iterators::Pair<Rule>,
-                        expr : AstNode) -> AstNode {
-    let mut pair = pair.into_inner();
-    let verb = pair.next().unwrap();
-    let adverbs : Vec<pest::iterators::Pair<_>> = pair.collect();
-
-    match verb.as_str() {
-        ">:" => {
-            assert_eq!(adverbs.len(), 0);
-            AstNode::MonadicOp { verb: MonadicVerb::Increment,
-                expr: Box::new(expr) }
-        },
-        "*:" => {
-            assert_eq!(adverbs.len(), 0);
-            AstNode::MonadicOp { verb: MonadicVerb::Square,
-                expr: Box::new(expr) }
-        },
-        "-" => {
-            match adverbs.len() {
-                0 => AstNode::MonadicOp { verb: MonadicVerb::Negate,
-                    expr: Box::new(expr) },
-                1 => AstNode::Reduce { verb: DyadicVerb::Minus,
-                    expr: Box::new(expr) },
-                _ => panic!("Unsupported number of adverbs for '-': {}", adverbs.len())
-            }
-        },
-        "%" => {
-            assert_eq!(adverbs.len(), 0);
-            AstNode::MonadicOp { verb: MonadicVerb::Reciprocal,
-                expr: Box::new(expr) }
-        },
-        "#" => {
-            assert_eq!(adverbs.len(), 0);
-            AstNode::MonadicOp { verb: MonadicVerb::Tally,
-                expr: Box::new(expr) }
-        },
-        ">." => {
-            match adverbs.len() {
-                0 => AstNode::MonadicOp { verb: MonadicVerb::Ceiling,
-                    expr: Box::new(expr) },
-                1 => AstNode::Reduce { verb: DyadicVerb::LargerOf,
-                    expr: Box::new(expr) },
-                _ => panic!("Unsupported number of adverbs for '>.': {}", adverbs.len())
-            }
-        },
-        "+" => {
-            assert_eq!(adverbs.len(), 1);
-            assert_eq!(adverbs[0].as_str(), "/");
-            AstNode::Reduce { verb: DyadicVerb::Plus,
-                expr: Box::new(expr) }
-        },
-        "*" => {
-            assert_eq!(adverbs.len(), 1);
-            assert_eq!(adverbs[0].as_str(), "/");
-            AstNode::Reduce { verb: DyadicVerb::Times,
-                expr: Box::new(expr) }
+/// Builds the monadic `AstNode` for a parsed `VerbPhrase`: a plain verb
+/// becomes `MonadicOp`, a verb with the reduce/insert adverb (`+/`, `-/`, ...)
+/// becomes `Reduce`. Adding a new adverb just means adding a `VerbPhrase`
+/// variant and a match arm here, instead of auditing `adverbs.len()` checks.
+/// `\` (scan) parses to its own `VerbPhrase::Scan`, but there's no AST node
+/// for it yet, so it errors out instead of being silently treated as `/`.
+fn parse_monadic_action(verb_phrase: VerbPhrase, expr: AstNode) -> AstNode {
+    match verb_phrase {
+        VerbPhrase::Plain(verb) => match verb.as_str() {
+            ">:" => AstNode::MonadicOp { verb: MonadicVerb::Increment, expr: Box::new(expr) },
+            "*:" => AstNode::MonadicOp { verb: MonadicVerb::Square, expr: Box::new(expr) },
+            "-" => AstNode::MonadicOp { verb: MonadicVerb::Negate, expr: Box::new(expr) },
+            "%" => AstNode::MonadicOp { verb: MonadicVerb::Reciprocal, expr: Box::new(expr) },
+            "#" => AstNode::MonadicOp { verb: MonadicVerb::Tally, expr: Box::new(expr) },
+            ">." => AstNode::MonadicOp { verb: MonadicVerb::Ceiling, expr: Box::new(expr) },
+            "$" => AstNode::MonadicOp { verb: MonadicVerb::ShapeOf, expr: Box::new(expr) },
+            "+" | "*" => panic!("'{}' has no monadic meaning; did you mean '{}/'?", verb, verb),
+            _ => panic!("Unsupported monadic action verb: {}", verb),
         },
-        "$" => {
-            assert_eq!(adverbs.len(), 0);
-            AstNode::MonadicOp { verb: MonadicVerb::ShapeOf,
-                expr: Box::new(expr) }
+        VerbPhrase::Reduce(verb) => match verb.as_str() {
+            "-" => AstNode::Reduce { verb: DyadicVerb::Minus, expr: Box::new(expr) },
+            ">." => AstNode::Reduce { verb: DyadicVerb::LargerOf, expr: Box::new(expr) },
+            "+" => AstNode::Reduce { verb: DyadicVerb::Plus, expr: Box::new(expr) },
+            "*" => AstNode::Reduce { verb: DyadicVerb::Times, expr: Box::new(expr) },
+            _ => panic!("'{}/' is not a supported reduce", verb),
         },
-        _ => panic!("Unsupported monadic action verb: {}", verb.as_str()),
+        VerbPhrase::Scan(verb) => panic!("'{}\\' (scan) is not yet supported", verb),
     }
 }
 
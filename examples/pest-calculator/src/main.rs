@@ -1,61 +1,146 @@
 use pest::iterators::Pairs;
 use pest::pratt_parser::PrattParser;
 use pest::Parser;
+use pest::Span;
+use std::collections::HashMap;
 use std::io::{self, BufRead};
+use std::num::IntErrorKind;
 
 #[derive(pest_derive::Parser)]
 #[grammar = "calculator.pest"]
 pub struct CalculatorParser;
 
+/// Variable bindings made by `assignment` statements, kept alive across
+/// REPL iterations so later lines can refer to earlier ones.
+pub type SymbolTable = HashMap<String, f64>;
+
 lazy_static::lazy_static! {
     static ref PRATT_PARSER: PrattParser<Rule> = {
         use pest::pratt_parser::{Assoc::*, Op};
         use Rule::*;
 
-        // Precedence is defined lowest to highest
+        // Precedence is defined lowest to highest.
+        // The ternary sits above this table entirely: it's parsed by the
+        // `ternary` grammar rule rather than as an `Op::infix` level, since
+        // it has two operators and three operands.
         PrattParser::new()
-            // Addition and subtract have equal precedence
+            .op(Op::infix(or, Left))
+            .op(Op::infix(and, Left))
+            .op(Op::infix(eq, Left) | Op::infix(neq, Left))
+            .op(Op::infix(lt, Left) | Op::infix(lte, Left) | Op::infix(gt, Left) | Op::infix(gte, Left))
             .op(Op::infix(add, Left) | Op::infix(subtract, Left))
             .op(Op::infix(multiply, Left) | Op::infix(divide, Left) | Op::infix(modulo, Left))
+            .op(Op::infix(power, Right))
             .op(Op::prefix(unary_minus))
     };
 }
 
 #[derive(Debug)]
-pub enum Expr {
-    Integer(i32),
-    UnaryMinus(Box<Expr>),
+pub enum Expr<'i> {
+    // Kept as the raw literal `Span` rather than an already-parsed `i32` so
+    // that a bad literal (too big, leading zeroes aside) surfaces as an
+    // `EvalError::NumberParse`/`EvalError::Overflow` at eval time instead of
+    // an `unwrap` panic while building the tree.
+    Integer(Span<'i>),
+    Bool(bool),
+    Ident(Span<'i>),
+    UnaryMinus(Span<'i>, Box<Expr<'i>>),
     BinOp {
-        lhs: Box<Expr>,
+        lhs: Box<Expr<'i>>,
         op: Op,
-        rhs: Box<Expr>,
+        op_span: Span<'i>,
+        rhs: Box<Expr<'i>>,
+    },
+    Ternary {
+        question_span: Span<'i>,
+        cond: Box<Expr<'i>>,
+        then_branch: Box<Expr<'i>>,
+        else_branch: Box<Expr<'i>>,
     },
 }
 
+/// A top-level input line: either `ident = ternary`, which binds a variable
+/// and produces no value, or a bare `ternary` to evaluate.
+#[derive(Debug)]
+pub enum Statement<'i> {
+    Assignment(Span<'i>, Expr<'i>),
+    Expression(Expr<'i>),
+}
+
+/// Parses the `statement` rule, i.e. the single alternative (`assignment` or
+/// `ternary`) wrapped inside it.
+pub fn parse_statement(pairs: Pairs<Rule>) -> Statement {
+    let statement = pairs.peek().unwrap();
+    match statement.as_rule() {
+        Rule::assignment => {
+            let mut inner = statement.into_inner();
+            let ident = inner.next().unwrap().as_span();
+            let expr = parse_ternary(inner.next().unwrap().into_inner());
+            Statement::Assignment(ident, expr)
+        }
+        Rule::ternary => Statement::Expression(parse_ternary(statement.into_inner())),
+        rule => unreachable!("Statement::parse expected assignment or ternary, found {:?}", rule),
+    }
+}
+
+/// Parses the `ternary ? a : b` form, recursing into [`parse_expr`] for the
+/// operator table and into itself for the (right-associative) branches.
+pub fn parse_ternary(mut pairs: Pairs<Rule>) -> Expr {
+    let cond = parse_expr(pairs.next().unwrap().into_inner());
+    match pairs.next() {
+        Some(question) => {
+            let question_span = question.as_span();
+            let then_branch = parse_ternary(pairs.next().unwrap().into_inner());
+            let _colon = pairs.next().unwrap();
+            let else_branch = parse_ternary(pairs.next().unwrap().into_inner());
+            Expr::Ternary {
+                question_span,
+                cond: Box::new(cond),
+                then_branch: Box::new(then_branch),
+                else_branch: Box::new(else_branch),
+            }
+        }
+        None => cond,
+    }
+}
+
 pub fn parse_expr(pairs: Pairs<Rule>) -> Expr {
     PRATT_PARSER
         .map_primary(|primary| match primary.as_rule() {
-            Rule::integer => Expr::Integer(primary.as_str().parse::<i32>().unwrap()),
-            Rule::expr => parse_expr(primary.into_inner()),
+            Rule::integer => Expr::Integer(primary.as_span()),
+            Rule::bool => Expr::Bool(primary.as_str() == "true"),
+            Rule::ident => Expr::Ident(primary.as_span()),
+            Rule::ternary => parse_ternary(primary.into_inner()),
             rule => unreachable!("Expr::parse expected atom, found {:?}", rule),
         })
         .map_infix(|lhs, op, rhs| {
+            let op_span = op.as_span();
             let op = match op.as_rule() {
+                Rule::or => Op::Or,
+                Rule::and => Op::And,
+                Rule::eq => Op::Eq,
+                Rule::neq => Op::NotEq,
+                Rule::lt => Op::Lt,
+                Rule::lte => Op::Lte,
+                Rule::gt => Op::Gt,
+                Rule::gte => Op::Gte,
                 Rule::add => Op::Add,
                 Rule::subtract => Op::Subtract,
                 Rule::multiply => Op::Multiply,
                 Rule::divide => Op::Divide,
                 Rule::modulo => Op::Modulo,
+                Rule::power => Op::Power,
                 rule => unreachable!("Expr::parse expected infix operation, found {:?}", rule),
             };
             Expr::BinOp {
                 lhs: Box::new(lhs),
                 op,
+                op_span,
                 rhs: Box::new(rhs),
             }
         })
         .map_prefix(|op, rhs| match op.as_rule() {
-            Rule::unary_minus => Expr::UnaryMinus(Box::new(rhs)),
+            Rule::unary_minus => Expr::UnaryMinus(op.as_span(), Box::new(rhs)),
             _ => unreachable!(),
         })
         .parse(pairs)
@@ -68,17 +153,206 @@ pub enum Op {
     Multiply,
     Divide,
     Modulo,
+    Power,
+    Or,
+    And,
+    Eq,
+    NotEq,
+    Lt,
+    Lte,
+    Gt,
+    Gte,
+}
+
+/// A runtime value: `true`/`false` and relational/logical operators produce
+/// `Bool`, integers and arithmetic produce `Number`, and the ternary yields
+/// whichever value its chosen branch produces.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Value {
+    Number(f64),
+    Bool(bool),
+}
+
+/// An evaluation-time failure, carrying the `Span` of the literal or
+/// operator responsible so it can be reported the way a parse error would.
+#[derive(Debug)]
+pub enum EvalError<'i> {
+    DivisionByZero(Span<'i>),
+    Overflow(Span<'i>),
+    NonIntegerArgument(Span<'i>),
+    NumberParse(Span<'i>),
+    UndefinedVariable(Span<'i>),
+}
+
+fn as_number<'i>(value: Value, span: Span<'i>) -> Result<f64, EvalError<'i>> {
+    match value {
+        Value::Number(n) => Ok(n),
+        Value::Bool(_) => Err(EvalError::NonIntegerArgument(span)),
+    }
+}
+
+fn as_bool<'i>(value: Value, span: Span<'i>) -> Result<bool, EvalError<'i>> {
+    match value {
+        Value::Bool(b) => Ok(b),
+        Value::Number(_) => Err(EvalError::NonIntegerArgument(span)),
+    }
+}
+
+/// Evaluates a top-level line: an `assignment` updates `table` and yields no
+/// value, a bare expression leaves `table` untouched and yields its `Value`.
+pub fn eval_statement<'i>(
+    statement: &Statement<'i>,
+    table: &mut SymbolTable,
+) -> Result<Option<Value>, EvalError<'i>> {
+    match statement {
+        Statement::Assignment(ident, expr) => {
+            let value = as_number(eval(expr, table)?, *ident)?;
+            table.insert(ident.as_str().to_string(), value);
+            Ok(None)
+        }
+        Statement::Expression(expr) => eval(expr, table).map(Some),
+    }
+}
+
+/// Walks the `Expr` tree built by [`parse_expr`]/[`parse_ternary`], only
+/// evaluating the operands `&&`, `||` and the ternary actually need so that
+/// they short-circuit.
+pub fn eval<'i>(expr: &Expr<'i>, table: &SymbolTable) -> Result<Value, EvalError<'i>> {
+    match expr {
+        Expr::Integer(span) => span
+            .as_str()
+            .parse::<i32>()
+            .map(|n| Value::Number(n as f64))
+            .map_err(|e| match e.kind() {
+                IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => {
+                    EvalError::Overflow(*span)
+                }
+                _ => EvalError::NumberParse(*span),
+            }),
+        Expr::Bool(b) => Ok(Value::Bool(*b)),
+        Expr::Ident(span) => table
+            .get(span.as_str())
+            .map(|n| Value::Number(*n))
+            .ok_or(EvalError::UndefinedVariable(*span)),
+        Expr::UnaryMinus(span, rhs) => Ok(Value::Number(-as_number(eval(rhs, table)?, *span)?)),
+        Expr::Ternary {
+            question_span,
+            cond,
+            then_branch,
+            else_branch,
+        } => {
+            if as_bool(eval(cond, table)?, *question_span)? {
+                eval(then_branch, table)
+            } else {
+                eval(else_branch, table)
+            }
+        }
+        Expr::BinOp {
+            lhs,
+            op,
+            op_span,
+            rhs,
+        } => match op {
+            Op::Or => {
+                if as_bool(eval(lhs, table)?, *op_span)? {
+                    Ok(Value::Bool(true))
+                } else {
+                    Ok(Value::Bool(as_bool(eval(rhs, table)?, *op_span)?))
+                }
+            }
+            Op::And => {
+                if as_bool(eval(lhs, table)?, *op_span)? {
+                    Ok(Value::Bool(as_bool(eval(rhs, table)?, *op_span)?))
+                } else {
+                    Ok(Value::Bool(false))
+                }
+            }
+            Op::Eq => Ok(Value::Bool(eval(lhs, table)? == eval(rhs, table)?)),
+            Op::NotEq => Ok(Value::Bool(eval(lhs, table)? != eval(rhs, table)?)),
+            Op::Lt => Ok(Value::Bool(
+                as_number(eval(lhs, table)?, *op_span)? < as_number(eval(rhs, table)?, *op_span)?,
+            )),
+            Op::Lte => Ok(Value::Bool(
+                as_number(eval(lhs, table)?, *op_span)? <= as_number(eval(rhs, table)?, *op_span)?,
+            )),
+            Op::Gt => Ok(Value::Bool(
+                as_number(eval(lhs, table)?, *op_span)? > as_number(eval(rhs, table)?, *op_span)?,
+            )),
+            Op::Gte => Ok(Value::Bool(
+                as_number(eval(lhs, table)?, *op_span)? >= as_number(eval(rhs, table)?, *op_span)?,
+            )),
+            Op::Add => Ok(Value::Number(
+                as_number(eval(lhs, table)?, *op_span)? + as_number(eval(rhs, table)?, *op_span)?,
+            )),
+            Op::Subtract => Ok(Value::Number(
+                as_number(eval(lhs, table)?, *op_span)? - as_number(eval(rhs, table)?, *op_span)?,
+            )),
+            Op::Multiply => Ok(Value::Number(
+                as_number(eval(lhs, table)?, *op_span)? * as_number(eval(rhs, table)?, *op_span)?,
+            )),
+            Op::Divide => {
+                let lhs = as_number(eval(lhs, table)?, *op_span)?;
+                let rhs = as_number(eval(rhs, table)?, *op_span)?;
+                if rhs == 0.0 {
+                    Err(EvalError::DivisionByZero(*op_span))
+                } else {
+                    Ok(Value::Number(lhs / rhs))
+                }
+            }
+            Op::Modulo => {
+                let lhs = as_number(eval(lhs, table)?, *op_span)?;
+                let rhs = as_number(eval(rhs, table)?, *op_span)?;
+                if rhs == 0.0 {
+                    Err(EvalError::DivisionByZero(*op_span))
+                } else {
+                    Ok(Value::Number(lhs % rhs))
+                }
+            }
+            Op::Power => {
+                let result = as_number(eval(lhs, table)?, *op_span)?.powf(as_number(eval(rhs, table)?, *op_span)?);
+                if result.is_infinite() {
+                    Err(EvalError::Overflow(*op_span))
+                } else {
+                    Ok(Value::Number(result))
+                }
+            }
+        },
+    }
+}
+
+fn print_eval_error(error: &EvalError) {
+    let (span, message) = match error {
+        EvalError::DivisionByZero(span) => (span, "division by zero"),
+        EvalError::Overflow(span) => (span, "result is too large to represent"),
+        EvalError::NonIntegerArgument(span) => (span, "operand has the wrong type"),
+        EvalError::NumberParse(span) => (span, "not a valid number"),
+        EvalError::UndefinedVariable(span) => (span, "undefined variable"),
+    };
+    let pest_error: pest::error::Error<Rule> = pest::error::Error::new_from_span(
+        pest::error::ErrorVariant::CustomError {
+            message: message.to_string(),
+        },
+        *span,
+    );
+    eprintln!("{}", pest_error);
 }
 
 fn main() -> io::Result<()> {
+    let mut table = SymbolTable::new();
+
     for line in io::stdin().lock().lines() {
-        match CalculatorParser::parse(Rule::equation, &line?) {
+        let line = line?;
+        match CalculatorParser::parse(Rule::equation, &line) {
             Ok(mut pairs) => {
-                println!(
-                    "Parsed: {:#?}",
-                    // inner of expr
-                    parse_expr(pairs.next().unwrap().into_inner())
+                let statement = parse_statement(
+                    // inner of statement
+                    pairs.next().unwrap().into_inner(),
                 );
+                match eval_statement(&statement, &mut table) {
+                    Ok(Some(value)) => println!(" = {:?}", value),
+                    Ok(None) => (),
+                    Err(e) => print_eval_error(&e),
+                }
             }
             Err(e) => {
                 eprintln!("Parse failed: {:?}", e);
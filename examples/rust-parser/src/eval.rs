@@ -0,0 +1,296 @@
+//! A tree-walking evaluator over the `precedence::Expr` tree.
+//!
+//! Literals are decoded to a runtime [`Value`], unary/binary operators are
+//! applied with the precedence already encoded in the tree, and `call`
+//! nodes are dispatched against a caller-supplied [`Functions`] table keyed
+//! by `ident`. Every failure carries the offending [`Span`] so a type
+//! mismatch (e.g. multiplying a string by a bool) points back into the
+//! source that caused it.
+
+use std::collections::HashMap;
+
+use pest::iterators::Pair;
+use pest::Span;
+
+use crate::precedence::{self, Expr};
+use crate::Rule;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Int(i64),
+    Float(f64),
+    Bool(bool),
+    Char(char),
+    Str(String),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum EvalError<'i> {
+    TypeMismatch { span: Span<'i>, message: String },
+    DivisionByZero(Span<'i>),
+    NumberParse(Span<'i>),
+    UndefinedFunction(Span<'i>),
+    CallError { span: Span<'i>, message: String },
+}
+
+/// Functions available to `call` nodes, keyed by `ident`. A `.`-call passes
+/// the receiver as the first argument; a bare `ident` used as a value calls
+/// the matching zero-arg function.
+pub type Functions<'f> = HashMap<&'f str, fn(&[Value]) -> Result<Value, String>>;
+
+pub fn eval<'i>(expr: &Expr<'i>, functions: &Functions) -> Result<Value, EvalError<'i>> {
+    match expr {
+        Expr::Term(pair) => eval_term(pair.clone(), functions),
+        Expr::Binary { lhs, op, rhs } => {
+            let lhs = eval(lhs, functions)?;
+            let rhs = eval(rhs, functions)?;
+            eval_binary(lhs, op, rhs)
+        }
+    }
+}
+
+fn eval_term<'i>(term: Pair<'i, Rule>, functions: &Functions) -> Result<Value, EvalError<'i>> {
+    let mut inner = term.into_inner().peekable();
+
+    let mut prefixes = Vec::new();
+    while let Some(pair) = inner.peek() {
+        match pair.as_rule() {
+            Rule::op_unary_minus | Rule::op_unary_not => prefixes.push(inner.next().unwrap()),
+            _ => break,
+        }
+    }
+
+    let value_pair = inner.next().expect("term always has a value after its prefixes");
+    let literal = value_pair
+        .into_inner()
+        .next()
+        .expect("value always wraps exactly one literal or ident");
+    let mut value = eval_value(literal, functions)?;
+
+    while let Some(dot) = inner.next() {
+        debug_assert_eq!(dot.as_rule(), Rule::dot);
+        let call = inner.next().expect("dot is always followed by a call");
+        value = eval_call(call, Some(value), functions)?;
+    }
+
+    for prefix in prefixes.into_iter().rev() {
+        value = apply_prefix(prefix, value)?;
+    }
+
+    Ok(value)
+}
+
+fn eval_value<'i>(pair: Pair<'i, Rule>, functions: &Functions) -> Result<Value, EvalError<'i>> {
+    match pair.as_rule() {
+        Rule::int => {
+            let span = pair.as_span();
+            pair.as_str()
+                .replace('_', "")
+                .parse()
+                .map(Value::Int)
+                .map_err(|_| EvalError::NumberParse(span))
+        }
+        Rule::float => {
+            let span = pair.as_span();
+            pair.as_str()
+                .replace('_', "")
+                .parse()
+                .map(Value::Float)
+                .map_err(|_| EvalError::NumberParse(span))
+        }
+        Rule::bool => Ok(Value::Bool(pair.as_str() == "true")),
+        Rule::chr => Ok(Value::Char(eval_chr(pair))),
+        Rule::string | Rule::byte_string_lit => Ok(Value::Str(eval_escaped_string(pair))),
+        Rule::raw_string_lit | Rule::byte_raw_string_lit => {
+            let inner = pair
+                .into_inner()
+                .next()
+                .expect("raw string literals always wrap a raw_string_lit_inner");
+            Ok(Value::Str(inner.as_str().to_string()))
+        }
+        Rule::ident => {
+            let span = pair.as_span();
+            let function = functions
+                .get(pair.as_str())
+                .ok_or(EvalError::UndefinedFunction(span))?;
+            function(&[]).map_err(|message| EvalError::CallError { span, message })
+        }
+        rule => unreachable!("value only ever wraps a literal or ident, got {:?}", rule),
+    }
+}
+
+fn eval_call<'i>(
+    call: Pair<'i, Rule>,
+    receiver: Option<Value>,
+    functions: &Functions,
+) -> Result<Value, EvalError<'i>> {
+    let span = call.as_span();
+    let mut inner = call.into_inner();
+    let name = inner
+        .next()
+        .expect("call always starts with an ident")
+        .as_str();
+
+    let mut args = Vec::new();
+    args.extend(receiver);
+    for pair in inner.filter(|pair| pair.as_rule() == Rule::expr) {
+        let expr = precedence::parse_expr(pair.into_inner());
+        args.push(eval(&expr, functions)?);
+    }
+
+    let function = functions
+        .get(name)
+        .ok_or(EvalError::UndefinedFunction(span))?;
+    function(&args).map_err(|message| EvalError::CallError { span, message })
+}
+
+fn apply_prefix<'i>(prefix: Pair<'i, Rule>, value: Value) -> Result<Value, EvalError<'i>> {
+    let span = prefix.as_span();
+    match (prefix.as_rule(), value) {
+        (Rule::op_unary_minus, Value::Int(n)) => Ok(Value::Int(-n)),
+        (Rule::op_unary_minus, Value::Float(n)) => Ok(Value::Float(-n)),
+        (Rule::op_unary_not, Value::Bool(b)) => Ok(Value::Bool(!b)),
+        (rule, value) => Err(EvalError::TypeMismatch {
+            span,
+            message: format!("{:?} cannot be applied to {:?}", rule, value),
+        }),
+    }
+}
+
+fn eval_binary<'i>(lhs: Value, op: &Pair<'i, Rule>, rhs: Value) -> Result<Value, EvalError<'i>> {
+    let span = op.as_span();
+    use Value::*;
+    match (op.as_rule(), lhs, rhs) {
+        (Rule::op_plus, Int(a), Int(b)) => Ok(Int(a + b)),
+        (Rule::op_plus, Float(a), Float(b)) => Ok(Float(a + b)),
+        (Rule::op_plus, Str(a), Str(b)) => Ok(Str(a + &b)),
+        (Rule::op_minus, Int(a), Int(b)) => Ok(Int(a - b)),
+        (Rule::op_minus, Float(a), Float(b)) => Ok(Float(a - b)),
+        (Rule::op_times, Int(a), Int(b)) => Ok(Int(a * b)),
+        (Rule::op_times, Float(a), Float(b)) => Ok(Float(a * b)),
+        (Rule::op_divide, Int(_), Int(0)) => Err(EvalError::DivisionByZero(span)),
+        (Rule::op_divide, Int(a), Int(b)) => Ok(Int(a / b)),
+        (Rule::op_divide, Float(a), Float(b)) => Ok(Float(a / b)),
+        (Rule::op_lt, Int(a), Int(b)) => Ok(Bool(a < b)),
+        (Rule::op_lte, Int(a), Int(b)) => Ok(Bool(a <= b)),
+        (Rule::op_gt, Int(a), Int(b)) => Ok(Bool(a > b)),
+        (Rule::op_gte, Int(a), Int(b)) => Ok(Bool(a >= b)),
+        (Rule::op_eq, a, b) => Ok(Bool(a == b)),
+        (Rule::op_neq, a, b) => Ok(Bool(a != b)),
+        (Rule::op_and, Bool(a), Bool(b)) => Ok(Bool(a && b)),
+        (Rule::op_or, Bool(a), Bool(b)) => Ok(Bool(a || b)),
+        (rule, lhs, rhs) => Err(EvalError::TypeMismatch {
+            span,
+            message: format!("cannot apply {:?} to {:?} and {:?}", rule, lhs, rhs),
+        }),
+    }
+}
+
+fn eval_chr(pair: Pair<Rule>) -> char {
+    let text = pair.as_str().to_string();
+    match pair.into_inner().next() {
+        Some(escape) => decode_escape(escape),
+        None => text
+            .chars()
+            .nth(1)
+            .expect("chr without an escape always wraps exactly one char"),
+    }
+}
+
+fn eval_escaped_string(pair: Pair<Rule>) -> String {
+    let mut decoded = String::new();
+    for part in pair.into_inner() {
+        match part.as_rule() {
+            Rule::raw_string => decoded.push_str(part.as_str()),
+            Rule::escape => decoded.push(decode_escape(part)),
+            rule => unreachable!("string/byte_string_lit only wrap raw_string/escape, got {:?}", rule),
+        }
+    }
+    decoded
+}
+
+fn decode_escape(escape: Pair<Rule>) -> char {
+    let inner = escape
+        .into_inner()
+        .next()
+        .expect("escape always wraps predefined/byte/unicode");
+    match inner.as_rule() {
+        Rule::predefined => match inner.as_str() {
+            "n" => '\n',
+            "r" => '\r',
+            "t" => '\t',
+            "\\" => '\\',
+            "0" => '\0',
+            "\"" => '"',
+            "'" => '\'',
+            other => unreachable!("grammar only allows known escapes, got {:?}", other),
+        },
+        Rule::byte => {
+            let hex = &inner.as_str()[1..];
+            u8::from_str_radix(hex, 16).expect("byte escape is always two hex digits") as char
+        }
+        Rule::unicode => {
+            let hex = inner
+                .into_inner()
+                .next()
+                .expect("unicode escape always wraps unicode_hex")
+                .as_str();
+            let code = u32::from_str_radix(hex, 16).expect("unicode_hex is always hex digits");
+            char::from_u32(code).unwrap_or(char::REPLACEMENT_CHARACTER)
+        }
+        rule => unreachable!("escape only ever wraps predefined/byte/unicode, got {:?}", rule),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pest::Parser;
+
+    fn eval_str<'f>(input: &'f str, functions: &Functions<'f>) -> Result<Value, EvalError<'f>> {
+        let pair = crate::RustParser::parse(Rule::expr, input)
+            .unwrap()
+            .next()
+            .unwrap();
+        eval(&precedence::parse_expr(pair.into_inner()), functions)
+    }
+
+    #[test]
+    fn arithmetic_respects_precedence() {
+        let functions = Functions::new();
+        assert_eq!(eval_str("1+2*3", &functions), Ok(Value::Int(7)));
+    }
+
+    #[test]
+    fn division_by_zero_reports_the_operator_span() {
+        let functions = Functions::new();
+        let err = eval_str("1/0", &functions).unwrap_err();
+        assert!(matches!(err, EvalError::DivisionByZero(span) if span.as_str() == "/"));
+    }
+
+    #[test]
+    fn calls_dispatch_through_the_function_table_with_the_receiver_as_first_arg() {
+        let mut functions = Functions::new();
+        functions.insert("a", |_args| Ok(Value::Float(2.0)));
+        functions.insert("cool", |args| match args {
+            [Value::Float(receiver), Value::Float(n), Value::Char(c)] => {
+                Ok(Value::Float(if *c == 'h' { receiver + n } else { 0.0 }))
+            }
+            _ => Err("cool expects (float, float, char)".to_string()),
+        });
+
+        // `a` is dispatched as a zero-arg call, then `.cool(1.0, 'h')` is
+        // dispatched with `a`'s result spliced in as the receiver.
+        assert_eq!(
+            eval_str("-a.cool(1.0,'h')", &functions),
+            Ok(Value::Float(-3.0))
+        );
+    }
+
+    #[test]
+    fn type_mismatch_points_at_the_operator_span() {
+        let functions = Functions::new();
+        let err = eval_str("1+\"boo\"", &functions).unwrap_err();
+        assert!(matches!(err, EvalError::TypeMismatch { span, .. } if span.as_str() == "+"));
+    }
+}
@@ -10,6 +10,11 @@ const _GRAMMAR: &'static str = include_str!("rust.pest");
 #[grammar = "rust.pest"]
 struct RustParser;
 
+pub mod eval;
+pub mod lint;
+pub mod precedence;
+pub mod recovery;
+
 #[test]
 fn true_lit() {
     parses_to! {
@@ -221,6 +226,66 @@ fn string_with_all_escape_types() {
     };
 }
 
+#[test]
+fn raw_string_literal() {
+    parses_to! {
+        parser: RustParser,
+        input: r####"r"a \n b""####,
+        rule: Rule::raw_string_lit,
+        tokens: [
+            raw_string_lit(0, 9, [
+                raw_string_lit_inner(2, 8)
+            ])
+        ]
+    };
+}
+
+#[test]
+fn raw_string_literal_with_hashes() {
+    parses_to! {
+        parser: RustParser,
+        input: r####"r#"a "quoted" b"#"####,
+        rule: Rule::raw_string_lit,
+        tokens: [
+            raw_string_lit(0, 17, [
+                raw_string_lit_inner(3, 15)
+            ])
+        ]
+    };
+}
+
+#[test]
+fn byte_string_literal() {
+    parses_to! {
+        parser: RustParser,
+        input: r#"b"a\nb""#,
+        rule: Rule::byte_string_lit,
+        tokens: [
+            byte_string_lit(0, 7, [
+                raw_string(2, 3),
+                escape(3, 5, [
+                    predefined(4, 5)
+                ]),
+                raw_string(5, 6)
+            ])
+        ]
+    };
+}
+
+#[test]
+fn byte_raw_string_literal() {
+    parses_to! {
+        parser: RustParser,
+        input: r####"br#"a "quoted" b"#"####,
+        rule: Rule::byte_raw_string_lit,
+        tokens: [
+            byte_raw_string_lit(0, 18, [
+                raw_string_lit_inner(4, 16)
+            ])
+        ]
+    };
+}
+
 #[test]
 fn char_without_escape() {
     parses_to! {
@@ -329,6 +394,44 @@ fn ident_underscore() {
     };
 }
 
+#[test]
+fn raw_ident() {
+    parses_to! {
+        parser: RustParser,
+        input: "r#fn",
+        rule: Rule::ident,
+        tokens: [
+            ident(0, 4)
+        ]
+    };
+}
+
+#[test]
+fn raw_ident_allows_reserved_prefix() {
+    parses_to! {
+        parser: RustParser,
+        input: "r#self2",
+        rule: Rule::ident,
+        tokens: [
+            ident(0, 7)
+        ]
+    };
+}
+
+#[test]
+fn raw_ident_rejects_reserved() {
+    // `r#self` isn't a legal raw identifier, so only the leading `r` is
+    // consumed as a plain ident and `#self` is left unparsed.
+    parses_to! {
+        parser: RustParser,
+        input: "r#self",
+        rule: Rule::ident,
+        tokens: [
+            ident(0, 1)
+        ]
+    };
+}
+
 #[test]
 fn expr_complex() {
     parses_to! {
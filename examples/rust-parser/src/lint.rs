@@ -0,0 +1,216 @@
+//! Unconditional self-recursion lint over function bodies.
+//!
+//! A function body is a sequence of [`Stmt`]s: `Stmt::Expr` wraps a parsed
+//! `expr` whose `call` nodes are call-graph edges (same as before), `If`
+//! splits control flow into a `then`/`else` pair of paths, and `Diverge` is
+//! a path terminator (panic, infinite loop, early return, …) that never
+//! falls through. The grammar has no statement or branch production yet,
+//! so callers build the `Stmt` sequence by hand; once it grows real
+//! statements, this becomes the shape a parser would produce instead.
+//!
+//! A function is flagged only when *every* control-flow path through its
+//! body reaches a call to itself before falling off the end. A self-call
+//! guarded behind one arm of an `if` whose other arm neither calls itself
+//! nor diverges is not unconditional and is not flagged; a diverging arm
+//! with no recursive call is a terminated path, not a reason to flag.
+
+use std::collections::HashMap;
+
+use pest::iterators::Pair;
+use pest::Span;
+
+use crate::Rule;
+
+/// One statement in a function body.
+pub enum Stmt<'i> {
+    /// An expression statement; any `call` nodes inside it are call-graph
+    /// edges.
+    Expr(Pair<'i, Rule>),
+    /// `if cond { then_branch } [else { else_branch }]`. A missing `else`
+    /// behaves like an empty one: the branch may simply fall through.
+    If {
+        then_branch: Vec<Stmt<'i>>,
+        else_branch: Option<Vec<Stmt<'i>>>,
+    },
+    /// A path terminator that never falls through to the statements after
+    /// it (panic, infinite loop, early return, …).
+    Diverge,
+}
+
+/// A function whose body unconditionally calls itself.
+#[derive(Debug, PartialEq)]
+pub struct Finding<'i> {
+    pub function: String,
+    pub span: Span<'i>,
+}
+
+enum PathResult<'i> {
+    /// Every step of this path is reached, ending in a call to the target.
+    Calls(Span<'i>),
+    /// This path never falls through to whatever follows it.
+    Diverges,
+    /// This path can reach the end without calling the target.
+    FallsThrough,
+}
+
+/// Flags every function in `bodies` (name -> body) whose body reaches a
+/// call to itself on every control-flow path.
+pub fn find_unconditional_self_recursion<'i>(
+    bodies: &HashMap<&str, Vec<Stmt<'i>>>,
+) -> Vec<Finding<'i>> {
+    let mut findings = Vec::new();
+    for (&name, body) in bodies {
+        if let PathResult::Calls(span) = always_calls(body, name) {
+            findings.push(Finding {
+                function: name.to_string(),
+                span,
+            });
+        }
+    }
+    findings
+}
+
+/// Whether `body` is guaranteed to reach a call to `name` on every path
+/// before falling off the end.
+fn always_calls<'i>(body: &[Stmt<'i>], name: &str) -> PathResult<'i> {
+    for stmt in body {
+        match stmt {
+            Stmt::Expr(pair) => {
+                if let Some(span) = first_self_call(pair.clone(), name) {
+                    return PathResult::Calls(span);
+                }
+            }
+            Stmt::Diverge => return PathResult::Diverges,
+            Stmt::If {
+                then_branch,
+                else_branch,
+            } => {
+                let then_result = always_calls(then_branch, name);
+                let else_result = match else_branch {
+                    Some(else_branch) => always_calls(else_branch, name),
+                    None => PathResult::FallsThrough,
+                };
+                match (then_result, else_result) {
+                    (PathResult::Calls(span), PathResult::Calls(_))
+                    | (PathResult::Calls(span), PathResult::Diverges)
+                    | (PathResult::Diverges, PathResult::Calls(span)) => {
+                        return PathResult::Calls(span)
+                    }
+                    (PathResult::Diverges, PathResult::Diverges) => return PathResult::Diverges,
+                    // At least one arm can fall through without calling, so
+                    // the `if` alone doesn't guarantee a call; keep
+                    // scanning the statements that follow it.
+                    _ => {}
+                }
+            }
+        }
+    }
+    PathResult::FallsThrough
+}
+
+/// The span of the first `call` anywhere inside `pair` whose `ident` is
+/// `name`.
+fn first_self_call<'i>(pair: Pair<'i, Rule>, name: &str) -> Option<Span<'i>> {
+    if pair.as_rule() == Rule::call {
+        let callee = pair.clone().into_inner().next()?.as_str();
+        if callee == name {
+            return Some(pair.as_span());
+        }
+    }
+    for child in pair.into_inner() {
+        if let Some(span) = first_self_call(child, name) {
+            return Some(span);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pest::Parser;
+
+    fn expr_stmt(input: &str) -> Stmt {
+        let pair = crate::RustParser::parse(Rule::expr, input)
+            .unwrap()
+            .next()
+            .unwrap();
+        Stmt::Expr(pair)
+    }
+
+    #[test]
+    fn flags_an_unguarded_self_call() {
+        let mut bodies = HashMap::new();
+        bodies.insert("recurse", vec![expr_stmt("x.recurse(1)")]);
+
+        let findings = find_unconditional_self_recursion(&bodies);
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].function, "recurse");
+    }
+
+    #[test]
+    fn does_not_flag_a_call_to_another_function() {
+        let mut bodies = HashMap::new();
+        bodies.insert("helper", vec![expr_stmt("x.other(1)")]);
+
+        assert!(find_unconditional_self_recursion(&bodies).is_empty());
+    }
+
+    #[test]
+    fn does_not_flag_recursion_guarded_by_a_branch_with_a_plain_exit() {
+        // if cond { x.recurse(1) } else { /* falls through, no call */ }
+        let mut bodies = HashMap::new();
+        bodies.insert(
+            "recurse",
+            vec![Stmt::If {
+                then_branch: vec![expr_stmt("x.recurse(1)")],
+                else_branch: Some(vec![]),
+            }],
+        );
+
+        assert!(find_unconditional_self_recursion(&bodies).is_empty());
+    }
+
+    #[test]
+    fn flags_recursion_reached_by_every_branch() {
+        // if cond { x.recurse(1) } else { y.recurse(2) }
+        let mut bodies = HashMap::new();
+        bodies.insert(
+            "recurse",
+            vec![Stmt::If {
+                then_branch: vec![expr_stmt("x.recurse(1)")],
+                else_branch: Some(vec![expr_stmt("y.recurse(2)")]),
+            }],
+        );
+
+        let findings = find_unconditional_self_recursion(&bodies);
+        assert_eq!(findings.len(), 1);
+    }
+
+    #[test]
+    fn a_diverging_branch_with_no_else_still_warns_on_the_unconditional_tail() {
+        // if cond { diverge } x.recurse(1)
+        let mut bodies = HashMap::new();
+        bodies.insert(
+            "recurse",
+            vec![
+                Stmt::If {
+                    then_branch: vec![Stmt::Diverge],
+                    else_branch: None,
+                },
+                expr_stmt("x.recurse(1)"),
+            ],
+        );
+
+        let findings = find_unconditional_self_recursion(&bodies);
+        assert_eq!(findings.len(), 1);
+    }
+
+    #[test]
+    fn diverging_without_a_self_call_is_not_flagged() {
+        let mut bodies = HashMap::new();
+        bodies.insert("terminates", vec![Stmt::Diverge]);
+
+        assert!(find_unconditional_self_recursion(&bodies).is_empty());
+    }
+}
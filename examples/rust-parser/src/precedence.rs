@@ -0,0 +1,129 @@
+//! Reshapes the flat `term (op term)*` stream produced by `Rule::expr` into
+//! a tree that reflects operator precedence and associativity, via
+//! precedence climbing.
+
+use pest::iterators::{Pair, Pairs};
+
+use crate::Rule;
+
+/// An `expr` reshaped so that operator precedence determines nesting,
+/// rather than the flat left-to-right token order the grammar produces.
+#[derive(Debug, PartialEq)]
+pub enum Expr<'i> {
+    Term(Pair<'i, Rule>),
+    Binary {
+        lhs: Box<Expr<'i>>,
+        op: Pair<'i, Rule>,
+        rhs: Box<Expr<'i>>,
+    },
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Assoc {
+    Left,
+    Right,
+}
+
+fn binding_power(rule: Rule) -> (u8, Assoc) {
+    match rule {
+        Rule::op_or => (1, Assoc::Left),
+        Rule::op_and => (2, Assoc::Left),
+        Rule::op_eq | Rule::op_neq => (3, Assoc::Left),
+        Rule::op_lt | Rule::op_lte | Rule::op_gt | Rule::op_gte => (4, Assoc::Left),
+        Rule::op_plus | Rule::op_minus => (5, Assoc::Left),
+        Rule::op_times | Rule::op_divide => (6, Assoc::Left),
+        rule => unreachable!("{:?} is not a binary operator produced by Rule::expr", rule),
+    }
+}
+
+/// Climbs the `Pairs` of a `Rule::expr` match, folding the flat
+/// `term (op term)*` sequence into a nested `Expr` tree.
+pub fn parse_expr(pairs: Pairs<Rule>) -> Expr {
+    let mut pairs = pairs.peekable();
+    let lhs = Expr::Term(pairs.next().expect("expr always starts with a term"));
+    climb(lhs, 0, &mut pairs)
+}
+
+fn climb<'i>(
+    mut lhs: Expr<'i>,
+    min_power: u8,
+    pairs: &mut std::iter::Peekable<Pairs<'i, Rule>>,
+) -> Expr<'i> {
+    while let Some(op) = pairs.peek() {
+        let (power, assoc) = binding_power(op.as_rule());
+        if power < min_power {
+            break;
+        }
+        let op = pairs.next().unwrap();
+        let mut rhs = Expr::Term(pairs.next().expect("operator always followed by a term"));
+
+        while let Some(next_op) = pairs.peek() {
+            let (next_power, _) = binding_power(next_op.as_rule());
+            let should_climb = match assoc {
+                Assoc::Left => next_power > power,
+                Assoc::Right => next_power >= power,
+            };
+            if !should_climb {
+                break;
+            }
+            rhs = climb(rhs, next_power, pairs);
+        }
+
+        lhs = Expr::Binary {
+            lhs: Box::new(lhs),
+            op,
+            rhs: Box::new(rhs),
+        };
+    }
+    lhs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pest::Parser;
+
+    fn parse(input: &str) -> Expr {
+        let expr = crate::RustParser::parse(Rule::expr, input)
+            .unwrap()
+            .next()
+            .unwrap();
+        parse_expr(expr.into_inner())
+    }
+
+    #[test]
+    fn single_term_has_no_binary_node() {
+        assert!(matches!(parse("1"), Expr::Term(_)));
+    }
+
+    #[test]
+    fn multiplication_nests_below_addition() {
+        // 1 + 2 * 3 should parse as 1 + (2 * 3), not (1 + 2) * 3.
+        match parse("1+2*3") {
+            Expr::Binary { lhs, op, rhs } => {
+                assert!(matches!(*lhs, Expr::Term(_)));
+                assert_eq!(op.as_rule(), Rule::op_plus);
+                match *rhs {
+                    Expr::Binary { op, .. } => assert_eq!(op.as_rule(), Rule::op_times),
+                    Expr::Term(_) => panic!("expected 2 * 3 to stay nested as a binary node"),
+                }
+            }
+            Expr::Term(_) => panic!("expected a binary expression"),
+        }
+    }
+
+    #[test]
+    fn same_precedence_is_left_associative() {
+        // 1 - 2 - 3 should parse as (1 - 2) - 3.
+        match parse("1-2-3") {
+            Expr::Binary { lhs, op, .. } => {
+                assert_eq!(op.as_rule(), Rule::op_minus);
+                match *lhs {
+                    Expr::Binary { op, .. } => assert_eq!(op.as_rule(), Rule::op_minus),
+                    Expr::Term(_) => panic!("expected 1 - 2 to nest on the left"),
+                }
+            }
+            Expr::Term(_) => panic!("expected a binary expression"),
+        }
+    }
+}
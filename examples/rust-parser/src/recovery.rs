@@ -0,0 +1,287 @@
+//! Error-recovery parsing for a `call`'s comma-separated argument list.
+//!
+//! A plain `RustParser::parse(Rule::call, ..)` aborts at the first argument
+//! that fails to parse. [`parse_call_recovering`] instead drives the
+//! grammar one argument at a time — `ident`, `paren_open`, then repeatedly
+//! `expr` — and on a failed `expr` match, inserts an [`ArgNode::Error`]
+//! placeholder and skips forward to the next synchronization token (`,` or
+//! `)`). That skip is grammar-aware: it re-tries the grammar's own literal
+//! rules (`string`, `chr`, …) at each position so a `(`, `)`, or `,` inside
+//! a string or char literal is skipped as part of that literal rather than
+//! mistaken for a synchronization point.
+//!
+//! The grammar has no repeatable "statement" production yet, so this only
+//! covers `call` argument lists; statement-level recovery will follow once
+//! one exists.
+//!
+//! Caveat: because each argument is (re-)parsed from its own offset
+//! substring, a successfully parsed [`ArgNode::Expr`]'s `Pair` reports
+//! spans relative to that substring, not `input` as a whole. Diagnostic
+//! spans are rebased onto `input`, since those only need a byte range, not
+//! a full parse tree.
+
+use pest::error::ErrorVariant;
+use pest::iterators::Pair;
+use pest::Parser;
+use pest::Span;
+
+use crate::{Rule, RustParser};
+
+/// One slot of a recovered argument list: either a successfully parsed
+/// `expr`, or a placeholder standing in for a slot that failed to parse.
+#[derive(Debug, PartialEq)]
+pub enum ArgNode<'i> {
+    Expr(Pair<'i, Rule>),
+    Error(Diagnostic<'i>),
+}
+
+/// A single recovered parse failure: where it happened, and which rules
+/// would have made it succeed.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic<'i> {
+    pub span: Span<'i>,
+    pub expected: Vec<Rule>,
+}
+
+/// A `call`'s callee name, its best-effort recovered argument nodes in
+/// source order, and every diagnostic collected along the way.
+#[derive(Debug, PartialEq)]
+pub struct RecoveredCall<'i> {
+    pub callee: &'i str,
+    pub args: Vec<ArgNode<'i>>,
+    pub diagnostics: Vec<Diagnostic<'i>>,
+}
+
+/// Parses `input` as `ident "(" (expr ("," expr)*)? ")"`, recovering from a
+/// failed argument instead of aborting the whole call.
+pub fn parse_call_recovering(input: &str) -> Result<RecoveredCall, Diagnostic> {
+    let (ident_pair, mut pos) =
+        try_parse(Rule::ident, input, 0).map_err(|e| to_diagnostic(input, 0, 0, &e))?;
+    let callee = ident_pair.as_str();
+
+    skip_whitespace(input, &mut pos);
+    let (_, after_paren) = try_parse(Rule::paren_open, input, pos)
+        .map_err(|e| to_diagnostic(input, pos, pos, &e))?;
+    pos = after_paren;
+
+    let mut args = Vec::new();
+    let mut diagnostics = Vec::new();
+
+    skip_whitespace(input, &mut pos);
+    if try_parse(Rule::paren_close, input, pos).is_ok() {
+        return Ok(RecoveredCall {
+            callee,
+            args,
+            diagnostics,
+        });
+    }
+
+    loop {
+        skip_whitespace(input, &mut pos);
+
+        match try_parse(Rule::expr, input, pos) {
+            Ok((pair, end)) => {
+                pos = end;
+                args.push(ArgNode::Expr(pair));
+            }
+            Err(error) => {
+                let start = pos;
+                let end = skip_to_sync(input, pos);
+                let diagnostic = to_diagnostic(input, start, end, &error);
+                pos = end;
+                diagnostics.push(diagnostic.clone());
+                args.push(ArgNode::Error(diagnostic));
+            }
+        }
+
+        skip_whitespace(input, &mut pos);
+        if let Ok((_, end)) = try_parse(Rule::comma, input, pos) {
+            pos = end;
+            continue;
+        }
+        if let Ok((_, end)) = try_parse(Rule::paren_close, input, pos) {
+            pos = end;
+            break;
+        }
+
+        // Neither a `,` nor the closing `)`: resync once more and, if
+        // there's still input left, keep trying to read arguments.
+        let start = pos;
+        let end = skip_to_sync(input, pos);
+        if end == start {
+            // No progress was made — e.g. the call ran off the end of
+            // `input` with no closing `)`. That's a real diagnostic in its
+            // own right, not just a quiet stopping point.
+            diagnostics.push(Diagnostic {
+                span: Span::new(input, start, start)
+                    .expect("recovery only ever advances along char boundaries"),
+                expected: vec![Rule::comma, Rule::paren_close],
+            });
+            break;
+        }
+        pos = end;
+    }
+
+    Ok(RecoveredCall {
+        callee,
+        args,
+        diagnostics,
+    })
+}
+
+fn try_parse<'i>(
+    rule: Rule,
+    input: &'i str,
+    offset: usize,
+) -> Result<(Pair<'i, Rule>, usize), pest::error::Error<Rule>> {
+    let pair = RustParser::parse(rule, &input[offset..])?
+        .next()
+        .expect("a successful parse always yields at least one pair");
+    let end = offset + pair.as_span().end();
+    Ok((pair, end))
+}
+
+fn to_diagnostic<'i>(
+    input: &'i str,
+    start: usize,
+    end: usize,
+    error: &pest::error::Error<Rule>,
+) -> Diagnostic<'i> {
+    let end = end.max(start);
+    Diagnostic {
+        span: Span::new(input, start, end)
+            .expect("recovery only ever advances along char boundaries"),
+        expected: expected_rules(error),
+    }
+}
+
+fn expected_rules(error: &pest::error::Error<Rule>) -> Vec<Rule> {
+    match &error.variant {
+        ErrorVariant::ParsingError { positives, .. } => positives.clone(),
+        ErrorVariant::CustomError { .. } => Vec::new(),
+    }
+}
+
+/// The atomic literal rules it's safe to skip as a single lexeme during
+/// recovery, so their contents (which may contain `(`, `)`, or `,`) can't
+/// be mistaken for synchronization points.
+const SKIPPABLE_LITERALS: &[Rule] = &[
+    Rule::byte_raw_string_lit,
+    Rule::byte_string_lit,
+    Rule::raw_string_lit,
+    Rule::string,
+    Rule::chr,
+    Rule::float,
+    Rule::int,
+    Rule::bool,
+    Rule::ident,
+];
+
+/// Advances from `pos` to the next top-level `,` or `)` (or the end of
+/// `input`), skipping over nested `(...)` groups and whole string/char/etc.
+/// literals rather than scanning character by character.
+fn skip_to_sync(input: &str, mut pos: usize) -> usize {
+    let mut depth = 0i32;
+    while pos < input.len() {
+        if depth == 0 {
+            if try_parse(Rule::comma, input, pos).is_ok()
+                || try_parse(Rule::paren_close, input, pos).is_ok()
+            {
+                return pos;
+            }
+        }
+
+        match input[pos..].chars().next() {
+            Some('(') => {
+                depth += 1;
+                pos += 1;
+            }
+            Some(')') => {
+                depth -= 1;
+                pos += 1;
+            }
+            Some(_) => match skip_one_literal(input, pos) {
+                Some(end) => pos = end,
+                None => pos += input[pos..].chars().next().unwrap().len_utf8(),
+            },
+            None => break,
+        }
+    }
+    pos
+}
+
+fn skip_one_literal(input: &str, pos: usize) -> Option<usize> {
+    SKIPPABLE_LITERALS.iter().find_map(|&rule| {
+        try_parse(rule, input, pos)
+            .ok()
+            .filter(|(_, end)| *end > pos)
+            .map(|(_, end)| end)
+    })
+}
+
+fn skip_whitespace(input: &str, pos: &mut usize) {
+    while let Some(c) = input[*pos..].chars().next() {
+        if c.is_whitespace() {
+            *pos += c.len_utf8();
+        } else {
+            break;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_valid_args_produce_no_diagnostics() {
+        let call = parse_call_recovering("f(1, 2, 3)").unwrap();
+        assert_eq!(call.callee, "f");
+        assert_eq!(call.args.len(), 3);
+        assert!(call.diagnostics.is_empty());
+        assert!(call.args.iter().all(|a| matches!(a, ArgNode::Expr(_))));
+    }
+
+    #[test]
+    fn a_bad_slot_does_not_abort_the_rest_of_the_call() {
+        let call = parse_call_recovering("f(1, @, 3)").unwrap();
+        assert_eq!(call.args.len(), 3);
+        assert_eq!(call.diagnostics.len(), 1);
+        assert!(matches!(call.args[0], ArgNode::Expr(_)));
+        assert!(matches!(call.args[1], ArgNode::Error(_)));
+        assert!(matches!(call.args[2], ArgNode::Expr(_)));
+    }
+
+    #[test]
+    fn an_unterminated_call_reports_a_diagnostic_instead_of_swallowing_it() {
+        let call = parse_call_recovering("f(1, 2").unwrap();
+        assert_eq!(call.args.len(), 2);
+        assert_eq!(call.diagnostics.len(), 1);
+        assert_eq!(call.diagnostics[0].expected, vec![Rule::comma, Rule::paren_close]);
+    }
+
+    #[test]
+    fn a_literal_comma_inside_a_string_argument_does_not_split_the_call() {
+        let call = parse_call_recovering("f(\"a,b\", 3)").unwrap();
+        assert_eq!(call.args.len(), 2);
+        assert!(call.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn a_literal_paren_inside_a_string_argument_does_not_split_the_call() {
+        let call = parse_call_recovering("f(\"(\", 2)").unwrap();
+        assert_eq!(call.args.len(), 2);
+        assert!(call.diagnostics.is_empty());
+    }
+
+    #[test]
+    fn recovery_skips_a_whole_string_lexeme_rather_than_stopping_at_its_inner_comma() {
+        // The bad token `@` is immediately followed by a valid string that
+        // contains a comma; recovery must treat the string as one lexeme
+        // and keep scanning past its inner comma to the real one after it.
+        let call = parse_call_recovering("f(@\"a,b\", 2)").unwrap();
+        assert_eq!(call.args.len(), 2);
+        assert_eq!(call.diagnostics.len(), 1);
+        assert_eq!(call.diagnostics[0].span.as_str(), "@\"a,b\"");
+    }
+}
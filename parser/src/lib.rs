@@ -221,6 +221,66 @@ fn string_with_all_escape_types() {
     };
 }
 
+#[test]
+fn raw_string_literal() {
+    parses_to! {
+        parser: RustParser,
+        input: r####"r"a \n b""####,
+        rule: Rule::raw_string_lit,
+        tokens: [
+            raw_string_lit(0, 9, [
+                raw_string_lit_inner(2, 8)
+            ])
+        ]
+    };
+}
+
+#[test]
+fn raw_string_literal_with_hashes() {
+    parses_to! {
+        parser: RustParser,
+        input: r####"r#"a "quoted" b"#"####,
+        rule: Rule::raw_string_lit,
+        tokens: [
+            raw_string_lit(0, 17, [
+                raw_string_lit_inner(3, 15)
+            ])
+        ]
+    };
+}
+
+#[test]
+fn byte_string_literal() {
+    parses_to! {
+        parser: RustParser,
+        input: r#"b"a\nb""#,
+        rule: Rule::byte_string_lit,
+        tokens: [
+            byte_string_lit(0, 7, [
+                raw_string(2, 3),
+                escape(3, 5, [
+                    predefined(4, 5)
+                ]),
+                raw_string(5, 6)
+            ])
+        ]
+    };
+}
+
+#[test]
+fn byte_raw_string_literal() {
+    parses_to! {
+        parser: RustParser,
+        input: r####"br#"a "quoted" b"#"####,
+        rule: Rule::byte_raw_string_lit,
+        tokens: [
+            byte_raw_string_lit(0, 18, [
+                raw_string_lit_inner(4, 16)
+            ])
+        ]
+    };
+}
+
 #[test]
 fn char_without_escape() {
     parses_to! {